@@ -10,11 +10,32 @@ use windows::Win32::Graphics::Dwm::*;
 #[cfg(target_os = "windows")]
 use windows::Win32::UI::WindowsAndMessaging::*;
 #[cfg(target_os = "windows")]
+use windows::Win32::UI::HiDpi::GetDpiForWindow;
+#[cfg(target_os = "windows")]
 use tauri_plugin_log::{Target, TargetKind};
+// 以下 macOS/Linux 专用 crate 需要在 Cargo.toml 的 target-specific dependencies 中声明：
+//   [target.'cfg(target_os = "macos")'.dependencies]
+//   cocoa = "0.25"
+//   objc = "0.2"
+//   [target.'cfg(target_os = "linux")'.dependencies]
+//   raw-window-handle = "0.6"
+//   x11rb = "0.13"
+#[cfg(target_os = "macos")]
+use cocoa::appkit::NSWindow;
+#[cfg(target_os = "macos")]
+use cocoa::base::{id, NO};
+#[cfg(target_os = "linux")]
+use raw_window_handle::{HasWindowHandle, RawWindowHandle};
+#[cfg(target_os = "linux")]
+use x11rb::connection::Connection;
+#[cfg(target_os = "linux")]
+use x11rb::protocol::xproto::{AtomEnum, ConnectionExt, PropMode};
 
-// 设置窗口透明度
+// 设置窗口透明度（跨平台：Windows / macOS / Linux）
 #[tauri::command]
 fn set_window_opacity(window: tauri::Window, opacity: f64) -> Result<(), String> {
+  let opacity = opacity.clamp(0.1, 1.0);
+
   #[cfg(target_os = "windows")]
   {
     use std::ffi::c_void;
@@ -26,27 +47,128 @@ fn set_window_opacity(window: tauri::Window, opacity: f64) -> Result<(), String>
       let ex_style = GetWindowLongW(hwnd, GWL_EXSTYLE);
       SetWindowLongW(hwnd, GWL_EXSTYLE, ex_style | WS_EX_LAYERED.0 as i32);
       // 设置窗口透明度
-      let alpha = (opacity.clamp(0.1, 1.0) * 255.0) as u8;
+      let alpha = (opacity * 255.0) as u8;
       SetLayeredWindowAttributes(hwnd, COLORREF(0), alpha, LWA_ALPHA)
         .map_err(|e| format!("Failed to set window opacity: {:?}", e))?;
     }
   }
+
+  #[cfg(target_os = "macos")]
+  {
+    let ns_window = window.ns_window().map_err(|e| e.to_string())? as id;
+    unsafe {
+      ns_window.setOpaque_(NO);
+      ns_window.setAlphaValue_(opacity);
+    }
+  }
+
+  #[cfg(target_os = "linux")]
+  {
+    let handle = window
+      .window_handle()
+      .map_err(|e| e.to_string())?
+      .as_raw();
+    // GTK-backed Tauri windows on Linux may hand back either an Xlib or an
+    // XCB handle depending on how the windowing backend connected; both give
+    // us the same numeric X11 window ID in their `window` field, just with
+    // different integer types.
+    let xid: u32 = match handle {
+      RawWindowHandle::Xlib(xlib_handle) => xlib_handle.window as u32,
+      RawWindowHandle::Xcb(xcb_handle) => xcb_handle.window.get(),
+      _ => return Err("Unsupported window handle for setting opacity".into()),
+    };
+
+    let (conn, _screen) = x11rb::connect(None).map_err(|e| e.to_string())?;
+    let opacity_atom = conn
+      .intern_atom(false, b"_NET_WM_WINDOW_OPACITY")
+      .map_err(|e| e.to_string())?
+      .reply()
+      .map_err(|e| e.to_string())?
+      .atom;
+
+    let value = (opacity * u32::MAX as f64) as u32;
+    conn
+      .change_property32(
+        PropMode::REPLACE,
+        xid,
+        opacity_atom,
+        AtomEnum::CARDINAL,
+        &[value],
+      )
+      .map_err(|e| e.to_string())?;
+    conn.flush().map_err(|e| e.to_string())?;
+  }
+
   Ok(())
 }
 
-// 设置窗口圆角
-fn set_window_corner(window: &tauri::WebviewWindow) -> Result<(), String> {
+// 设置鼠标穿透（点击穿透）模式
+// 开启后窗口仍可置于其他程序之上显示，但鼠标点击会穿透到底层窗口，
+// 适合作为悬浮 HUD 与日志/电台软件配合使用。
+// 注意：WS_EX_LAYERED 同时被 set_window_opacity 使用，这里只在启用时补充该位，
+// 关闭穿透时不清除它，避免重置用户已设置的透明度。第一次补充该位时，如果窗口
+// 还从未调用过 set_window_opacity 设置过 alpha，分层窗口在没有 alpha/颜色键的
+// 情况下可能整体不可见，因此这里顺带把它设成不透明，交由后续的透明度设置覆盖。
+#[tauri::command]
+fn set_click_through(window: tauri::Window, enable: bool) -> Result<(), String> {
+  #[cfg(target_os = "windows")]
+  {
+    use std::ffi::c_void;
+
+    let hwnd = HWND(window.hwnd().map_err(|e| e.to_string())?.0 as *mut c_void);
+
+    unsafe {
+      let ex_style = GetWindowLongW(hwnd, GWL_EXSTYLE);
+      let was_layered = ex_style & WS_EX_LAYERED.0 as i32 != 0;
+      let new_ex_style = if enable {
+        ex_style
+          | WS_EX_LAYERED.0 as i32
+          | WS_EX_TRANSPARENT.0 as i32
+          | WS_EX_TOPMOST.0 as i32
+          | WS_EX_APPWINDOW.0 as i32
+      } else {
+        ex_style & !(WS_EX_TRANSPARENT.0 as i32 | WS_EX_TOPMOST.0 as i32 | WS_EX_APPWINDOW.0 as i32)
+      };
+      SetWindowLongW(hwnd, GWL_EXSTYLE, new_ex_style);
+
+      if enable && !was_layered {
+        SetLayeredWindowAttributes(hwnd, COLORREF(0), 255, LWA_ALPHA)
+          .map_err(|e| format!("Failed to initialize layered window: {:?}", e))?;
+      }
+
+      // WS_EX_TOPMOST 只是样式位，真正让窗口进入/退出最顶层 Z 序要靠
+      // SetWindowPos 的 HWND_TOPMOST / HWND_NOTOPMOST，且不能带 SWP_NOZORDER
+      // （否则 hWndInsertAfter 会被忽略，Z 序完全不变）。
+      let insert_after = if enable { HWND_TOPMOST } else { HWND_NOTOPMOST };
+      SetWindowPos(
+        hwnd,
+        insert_after,
+        0,
+        0,
+        0,
+        0,
+        SWP_NOMOVE | SWP_NOSIZE | SWP_NOACTIVATE | SWP_FRAMECHANGED,
+      )
+      .map_err(|e| format!("Failed to set click-through: {:?}", e))?;
+    }
+  }
+  #[cfg(not(target_os = "windows"))]
+  {
+    let _ = (window, enable);
+  }
+  Ok(())
+}
+
+// 设置窗口圆角（内部实现，供 setup 与 set_window_corner 命令共用）
+#[cfg(target_os = "windows")]
+fn apply_window_corner(
+  window: &tauri::WebviewWindow,
+  corner_preference: DWM_WINDOW_CORNER_PREFERENCE,
+) -> Result<(), String> {
   use std::ffi::c_void;
 
   let hwnd = HWND(window.hwnd().map_err(|e| e.to_string())?.0 as *mut c_void);
 
-  // 圆角样式
-  // DWMWCP_DEFAULT = 0       // 系统默认
-  // DWMWCP_DONOTROUND = 1    // 不圆角
-  // DWMWCP_ROUND = 2         // 圆角
-  // DWMWCP_ROUNDSMALL = 3    // 小圆角
-  let corner_preference = DWMWCP_ROUND;
-
   unsafe {
     DwmSetWindowAttribute(
       hwnd,
@@ -59,6 +181,61 @@ fn set_window_corner(window: &tauri::WebviewWindow) -> Result<(), String> {
   Ok(())
 }
 
+// 设置窗口圆角样式（前端可调用，支持运行时切换）
+// preference 取值：default / donotround / round / roundsmall
+#[tauri::command]
+fn set_window_corner(window: tauri::WebviewWindow, preference: String) -> Result<(), String> {
+  #[cfg(target_os = "windows")]
+  {
+    // 圆角样式
+    // DWMWCP_DEFAULT = 0       // 系统默认
+    // DWMWCP_DONOTROUND = 1    // 不圆角
+    // DWMWCP_ROUND = 2         // 圆角
+    // DWMWCP_ROUNDSMALL = 3    // 小圆角
+    let corner_preference = match preference.as_str() {
+      "default" => DWMWCP_DEFAULT,
+      "donotround" => DWMWCP_DONOTROUND,
+      "round" => DWMWCP_ROUND,
+      "roundsmall" => DWMWCP_ROUNDSMALL,
+      other => return Err(format!("Unknown corner preference: {}", other)),
+    };
+    apply_window_corner(&window, corner_preference)?;
+  }
+  #[cfg(not(target_os = "windows"))]
+  {
+    let _ = (window, preference);
+  }
+  Ok(())
+}
+
+// 根据窗口所在显示器的 DPI 调整窗口大小（修复高 DPI 屏幕下窗口过小的问题）
+// 需要 windows crate 的 Win32_UI_HiDPI feature 以使用 GetDpiForWindow
+//
+// `base_logical_size` 必须是固定基准（配置文件里的逻辑尺寸，即 96 dpi 下的尺寸），
+// 每次都从这个基准重新计算物理尺寸，而不是从窗口当前（可能已经缩放过的）尺寸出发，
+// 否则窗口在不同 DPI 的显示器之间来回拖拽时，缩放会不断叠加而无法复原。
+#[cfg(target_os = "windows")]
+fn apply_dpi_scale(
+  window: &tauri::WebviewWindow,
+  base_logical_size: tauri::LogicalSize<f64>,
+) -> Result<(), String> {
+  use std::ffi::c_void;
+
+  let hwnd = HWND(window.hwnd().map_err(|e| e.to_string())?.0 as *mut c_void);
+  let dpi = unsafe { GetDpiForWindow(hwnd) };
+  let scale_factor = dpi as f64 / 96.0;
+
+  let scaled_size = tauri::PhysicalSize::new(
+    (base_logical_size.width * scale_factor) as u32,
+    (base_logical_size.height * scale_factor) as u32,
+  );
+
+  window
+    .set_size(scaled_size)
+    .map_err(|e| format!("Failed to resize window for DPI: {:?}", e))?;
+  Ok(())
+}
+
 fn main() {
   tauri::Builder::default()
     .plugin(tauri_plugin_dialog::init())
@@ -71,14 +248,39 @@ fn main() {
         .level(log::LevelFilter::Info)
         .build(),
     )
-    .invoke_handler(tauri::generate_handler![set_window_opacity])
+    .invoke_handler(tauri::generate_handler![
+      set_window_opacity,
+      set_window_corner,
+      set_click_through
+    ])
     .setup(|app| {
       // 获取主窗口
       let window = app.get_webview_window("main").unwrap();
-      // 设置窗口圆角（仅 Windows）
+      // 设置窗口圆角（仅 Windows，默认圆角）
       #[cfg(target_os = "windows")]
       {
-        set_window_corner(&window)?;
+        apply_window_corner(&window, DWMWCP_ROUND)?;
+      }
+      // 按当前显示器 DPI 调整窗口大小，并在窗口拖拽到不同 DPI 的显示器时重新计算
+      #[cfg(target_os = "windows")]
+      {
+        // 把启动时的物理尺寸换算回逻辑尺寸，作为后续所有缩放计算的固定基准
+        let initial_scale_factor = window.scale_factor().map_err(|e| e.to_string())?;
+        let base_logical_size = window
+          .inner_size()
+          .map_err(|e| e.to_string())?
+          .to_logical::<f64>(initial_scale_factor);
+
+        apply_dpi_scale(&window, base_logical_size)?;
+
+        let dpi_window = window.clone();
+        window.on_window_event(move |event| {
+          if let tauri::WindowEvent::ScaleFactorChanged { .. } = event {
+            if let Err(e) = apply_dpi_scale(&dpi_window, base_logical_size) {
+              log::error!("Failed to re-apply DPI scale: {}", e);
+            }
+          }
+        });
       }
     Ok(())
     })